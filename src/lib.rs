@@ -19,7 +19,8 @@
 use std::{
     io,
     io::{Read, Write},
-    net::TcpStream,
+    net::{IpAddr, TcpStream, ToSocketAddrs},
+    time::Duration,
 };
 
 use native_tls::{TlsConnector, TlsStream};
@@ -34,6 +35,12 @@ pub enum GopherError {
     #[error("Unsupported protocol")]
     UnsupportedProtocol,
 
+    #[error("SOCKS5 proxy error: {0}")]
+    SocksError(String),
+
+    #[error("{0}:{1} is a telnet resource, open it with an external telnet client")]
+    TelnetResource(String, u16),
+
     #[error(transparent)]
     HandshakeError(#[from] native_tls::HandshakeError<TcpStream>),
 
@@ -47,15 +54,29 @@ pub enum GopherError {
     UrlParseError(#[from] url::ParseError),
 }
 
+/// Default timeout applied to both the initial TCP connect and subsequent
+/// reads, unless overridden via [`Gopher::timeouts`].
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(8);
+
 /// The Gopher struct represents an initialized object ready to connect.
 pub struct Gopher {
     host: String,
     port: u16,
     tls: bool,
+    selector: String,
+    proxy: Option<String>,
+    opportunistic_tls: bool,
+    connect_timeout: Duration,
+    read_timeout: Duration,
 }
 
 impl Gopher {
-    /// Create a new `Gopher` object with the given endpoint.
+    /// Create a new `Gopher` object with the given endpoint. Supports
+    /// `gopher://` and `gophers://` schemes, including bracketed IPv6
+    /// host literals (`gopher://[2001:db8::1]:70/`). A `telnet://`
+    /// endpoint is recognized but returns
+    /// [`GopherError::TelnetResource`], since telnet sessions can't be
+    /// fetched like a regular Gopher resource.
     ///
     /// # Example
     /// ```
@@ -65,26 +86,133 @@ impl Gopher {
     pub fn new(endpoint: &str) -> Result<Self, GopherError> {
         let url = Url::parse(endpoint)?;
 
-        if url.host().is_none() {
-            return Err(GopherError::InvalidHost);
-        }
+        let host = match url.host() {
+            Some(host) => host,
+            None => return Err(GopherError::InvalidHost),
+        };
 
-        let (host, tls) = match url.scheme() {
-            "gopher" => (url.host().unwrap(), false),
-            "gophers" => (url.host().unwrap(), true),
+        let tls = match url.scheme() {
+            "gopher" => false,
+            "gophers" => true,
+            "telnet" => {
+                return Err(GopherError::TelnetResource(
+                    host.to_string(),
+                    url.port().unwrap_or(23),
+                ))
+            }
             _ => return Err(GopherError::UnsupportedProtocol),
         };
 
+        // `url::Host`'s `Display` already brackets IPv6 literals, so this
+        // round-trips `[2001:db8::1]` correctly instead of only handling
+        // plain domains.
+        let host = host.to_string();
+
+        let selector = match url.path() {
+            "" => "/".to_string(),
+            path => path.to_string(),
+        };
+
         Ok(Self {
-            host: host.to_string(),
+            host,
             port: url.port().unwrap_or(70),
             tls,
+            selector,
+            proxy: None,
+            opportunistic_tls: false,
+            connect_timeout: DEFAULT_TIMEOUT,
+            read_timeout: DEFAULT_TIMEOUT,
         })
     }
 
+    /// The parsed hostname or IP literal (IPv6 literals are bracketed,
+    /// e.g. `[2001:db8::1]`).
+    pub fn host(&self) -> &str {
+        &self.host
+    }
+
+    /// The port that will be connected to.
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+
+    /// Whether this connection will use TLS.
+    pub fn tls(&self) -> bool {
+        self.tls
+    }
+
+    /// The selector/path carried by the endpoint URL, so callers don't
+    /// have to split it out of the original string themselves.
+    pub fn selector(&self) -> &str {
+        &self.selector
+    }
+
+    /// Route this connection through a SOCKS5 proxy, such as a local Tor
+    /// daemon (`127.0.0.1:9050`). This is the only way to reach `.onion`
+    /// Gopher holes, since the hostname is resolved by the proxy rather
+    /// than locally. Chainable with the other builder methods below, so a
+    /// proxied connection can also get custom timeouts or opportunistic
+    /// TLS.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use gophers::Gopher;
+    /// let gopher = Gopher::new("gopher://bitreichxgczkzqp4ol.onion")
+    ///     .unwrap()
+    ///     .proxy("127.0.0.1:9050");
+    /// ```
+    pub fn proxy(mut self, proxy_addr: &str) -> Self {
+        self.proxy = Some(proxy_addr.to_string());
+        self
+    }
+
+    /// Opportunistically try TLS regardless of scheme, falling back to
+    /// plaintext if the handshake fails. Useful for servers on port 70
+    /// that may or may not speak TLS, where a `gopher://` URL would
+    /// otherwise never attempt encryption.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use gophers::Gopher;
+    /// let gopher = Gopher::new("gopher://bitreich.org")
+    ///     .unwrap()
+    ///     .opportunistic_tls();
+    /// let stream = gopher.connect().unwrap();
+    /// println!("encrypted: {}", stream.is_tls());
+    /// ```
+    pub fn opportunistic_tls(mut self) -> Self {
+        self.opportunistic_tls = true;
+        self
+    }
+
+    /// Set custom connect and read/write timeouts, instead of the 8
+    /// second default. This turns a hung or unreachable server into a
+    /// timely `io::Error` rather than a permanent hang.
+    ///
+    /// # Example
+    /// ```
+    /// use std::time::Duration;
+    /// use gophers::Gopher;
+    /// let gopher = Gopher::new("gophers://bitreich.org")
+    ///     .unwrap()
+    ///     .timeouts(Duration::from_secs(3), Duration::from_secs(10));
+    /// ```
+    pub fn timeouts(mut self, connect_timeout: Duration, read_timeout: Duration) -> Self {
+        self.connect_timeout = connect_timeout;
+        self.read_timeout = read_timeout;
+        self
+    }
+
     /// Establish a connection with a created Gopher object.
     /// Depending on `tls`, it will establish either a plain TCP or an
-    /// encrypted TLS connection.
+    /// encrypted TLS connection. If a SOCKS5 `proxy` was configured, the
+    /// TCP socket is first opened to the proxy and a SOCKS5 CONNECT
+    /// handshake is performed to reach the actual target, with TLS (if
+    /// any) negotiated on top of that tunnel. If `opportunistic_tls` is
+    /// set, a TLS handshake is always attempted first and, on failure, a
+    /// fresh plaintext TCP connection is dialed and returned in its place.
+    /// The socket honors `connect_timeout` for the initial
+    /// dial and `read_timeout` for subsequent reads and writes.
     ///
     /// # Example
     /// ```
@@ -93,17 +221,173 @@ impl Gopher {
     /// let mut stream = gopher.connect().unwrap();
     /// ```
     pub fn connect(&self) -> Result<GopherConnection, GopherError> {
-        let tcp_conn = TcpStream::connect(format!("{}:{}", self.host, self.port))?;
+        let tcp_conn = self.dial()?;
+
+        if self.opportunistic_tls {
+            return match TlsConnector::new()?.connect(bare_host(&self.host), tcp_conn) {
+                Ok(stream) => Ok(GopherConnection::Tls(stream)),
+                Err(_) => {
+                    // The TLS handshake consumed (and likely closed) the
+                    // original socket, so reconnect plaintext from scratch.
+                    Ok(GopherConnection::Tcp(self.dial()?))
+                }
+            };
+        }
 
         if !self.tls {
             return Ok(GopherConnection::Tcp(tcp_conn));
         }
 
         let tls_conn = TlsConnector::new()?;
-        let stream = tls_conn.connect(&self.host, tcp_conn)?;
+        // Bracketed IPv6 literals (`[2001:db8::1]`) must be passed bare,
+        // or native-tls's `IpAddr` detection misses them and sends the
+        // brackets as a (bogus) DNS hostname/SNI value.
+        let stream = tls_conn.connect(bare_host(&self.host), tcp_conn)?;
 
         Ok(GopherConnection::Tls(stream))
     }
+
+    /// Open the TCP socket (through the proxy, if any) honoring
+    /// `connect_timeout`, and apply `read_timeout` to both reads and
+    /// writes before handing it off for optional TLS negotiation.
+    fn dial(&self) -> Result<TcpStream, GopherError> {
+        let tcp_conn = match &self.proxy {
+            Some(proxy_addr) => {
+                let mut proxy_conn = connect_timeout(proxy_addr, self.connect_timeout)?;
+                // Apply before the handshake, not after, so a proxy that
+                // stalls mid-handshake still hits `read_timeout` instead
+                // of hanging forever.
+                proxy_conn.set_read_timeout(Some(self.read_timeout))?;
+                proxy_conn.set_write_timeout(Some(self.read_timeout))?;
+                socks5_connect(&mut proxy_conn, &self.host, self.port)?;
+                proxy_conn
+            }
+            None => {
+                let conn = connect_timeout(
+                    &format!("{}:{}", self.host, self.port),
+                    self.connect_timeout,
+                )?;
+                conn.set_read_timeout(Some(self.read_timeout))?;
+                conn.set_write_timeout(Some(self.read_timeout))?;
+                conn
+            }
+        };
+
+        Ok(tcp_conn)
+    }
+}
+
+/// Strip the surrounding brackets from an IPv6 literal host
+/// (`"[2001:db8::1]"` -> `"2001:db8::1"`), leaving domains and IPv4
+/// literals untouched.
+fn bare_host(host: &str) -> &str {
+    host.strip_prefix('[')
+        .and_then(|h| h.strip_suffix(']'))
+        .unwrap_or(host)
+}
+
+/// Resolve `addr` and try each candidate in turn, bounding every attempt
+/// by `timeout`, mirroring the multi-address fallback `TcpStream::connect`
+/// gives for free.
+fn connect_timeout(addr: &str, timeout: Duration) -> io::Result<TcpStream> {
+    let mut last_err = None;
+
+    for sock_addr in addr.to_socket_addrs()? {
+        match TcpStream::connect_timeout(&sock_addr, timeout) {
+            Ok(stream) => return Ok(stream),
+            Err(e) => last_err = Some(e),
+        }
+    }
+
+    Err(last_err
+        .unwrap_or_else(|| io::Error::new(io::ErrorKind::NotFound, "could not resolve address")))
+}
+
+/// Build the address portion (ATYP + address) of a SOCKS5 CONNECT
+/// request for `host`. IPv4/IPv6 literals (including the bracketed form
+/// `[::1]` produced by [`Gopher::host`]) are encoded with their raw
+/// address type; anything else is sent as a domain name and left for the
+/// proxy to resolve, which is essential for `.onion` addresses that
+/// cannot be resolved locally.
+fn socks5_address_bytes(host: &str) -> Result<Vec<u8>, GopherError> {
+    match bare_host(host).parse::<IpAddr>() {
+        Ok(IpAddr::V4(ip)) => {
+            let mut bytes = vec![0x01];
+            bytes.extend_from_slice(&ip.octets());
+            Ok(bytes)
+        }
+        Ok(IpAddr::V6(ip)) => {
+            let mut bytes = vec![0x04];
+            bytes.extend_from_slice(&ip.octets());
+            Ok(bytes)
+        }
+        Err(_) => {
+            if host.len() > u8::MAX as usize {
+                return Err(GopherError::SocksError("hostname too long".into()));
+            }
+            let mut bytes = vec![0x03, host.len() as u8];
+            bytes.extend_from_slice(host.as_bytes());
+            Ok(bytes)
+        }
+    }
+}
+
+/// Perform a SOCKS5 CONNECT handshake over `proxy_conn`, asking the proxy
+/// to open a connection to `host:port` on our behalf. See
+/// [`socks5_address_bytes`] for how `host` gets encoded.
+fn socks5_connect(proxy_conn: &mut TcpStream, host: &str, port: u16) -> Result<(), GopherError> {
+    // Greeting: version 5, one method, no-auth (0x00).
+    proxy_conn.write_all(&[0x05, 0x01, 0x00])?;
+
+    let mut reply = [0u8; 2];
+    proxy_conn.read_exact(&mut reply)?;
+    if reply[0] != 0x05 {
+        return Err(GopherError::SocksError("not a SOCKS5 proxy".into()));
+    }
+    if reply[1] != 0x00 {
+        return Err(GopherError::SocksError(
+            "proxy rejected no-auth method".into(),
+        ));
+    }
+
+    let mut req = vec![0x05, 0x01, 0x00];
+    req.extend(socks5_address_bytes(host)?);
+    req.extend_from_slice(&port.to_be_bytes());
+    proxy_conn.write_all(&req)?;
+
+    // Reply: VER REP RSV ATYP BND.ADDR BND.PORT
+    let mut head = [0u8; 4];
+    proxy_conn.read_exact(&mut head)?;
+    if head[0] != 0x05 {
+        return Err(GopherError::SocksError("malformed SOCKS5 reply".into()));
+    }
+    if head[1] != 0x00 {
+        return Err(GopherError::SocksError(format!(
+            "SOCKS5 CONNECT failed with code {}",
+            head[1]
+        )));
+    }
+
+    // Consume and discard BND.ADDR/BND.PORT, whose size depends on ATYP.
+    match head[3] {
+        0x01 => {
+            let mut buf = [0u8; 4 + 2];
+            proxy_conn.read_exact(&mut buf)?;
+        }
+        0x03 => {
+            let mut len = [0u8; 1];
+            proxy_conn.read_exact(&mut len)?;
+            let mut buf = vec![0u8; len[0] as usize + 2];
+            proxy_conn.read_exact(&mut buf)?;
+        }
+        0x04 => {
+            let mut buf = [0u8; 16 + 2];
+            proxy_conn.read_exact(&mut buf)?;
+        }
+        _ => return Err(GopherError::SocksError("unknown address type".into())),
+    }
+
+    Ok(())
 }
 
 /// Abstraction enum over TCP and TLS connections.
@@ -150,10 +434,343 @@ impl GopherConnection {
     /// assert_eq!(&data[..5], b"meme2");
     /// ```
     pub fn fetch(&mut self, path: &str) -> Result<Vec<u8>, io::Error> {
-        let req = format!("{}\r\n", path);
+        // Accept `?`-style queries as a convenience and translate them into
+        // the TAB-separated form that search (item type 7) selectors
+        // expect; see `search` for the canonical way to do this.
+        let req = match path.split_once('?') {
+            Some((selector, query)) => format!("{}\t{}\r\n", selector, query),
+            None => format!("{}\r\n", path),
+        };
         self.write_all(req.as_bytes())?;
         let mut buf = vec![];
         self.read_to_end(&mut buf)?;
         Ok(buf)
     }
+
+    /// Query a search server (item type `7`) by sending `selector` and
+    /// `query` separated by a TAB, per the Gopher protocol. Use this
+    /// instead of embedding a `?`-style query directly in [`fetch`]'s
+    /// path.
+    ///
+    /// [`fetch`]: GopherConnection::fetch
+    ///
+    /// # Example
+    /// ```no_run
+    /// use gophers::Gopher;
+    /// let gopher = Gopher::new("gopher://gopher.floodgap.com").unwrap();
+    /// let mut stream = gopher.connect().unwrap();
+    /// let results = stream.search("/v2/vs", "rust").unwrap();
+    /// ```
+    pub fn search(&mut self, selector: &str, query: &str) -> Result<Vec<u8>, io::Error> {
+        let req = format!("{}\t{}\r\n", selector, query);
+        self.write_all(req.as_bytes())?;
+        let mut buf = vec![];
+        self.read_to_end(&mut buf)?;
+        Ok(buf)
+    }
+
+    /// Like [`search`](GopherConnection::search), but parses the response
+    /// through the menu layer instead of returning raw bytes.
+    pub fn search_menu(&mut self, selector: &str, query: &str) -> Result<Vec<MenuItem>, io::Error> {
+        let data = self.search(selector, query)?;
+        Ok(parse_menu(&data))
+    }
+
+    /// Report whether this connection ended up encrypted. This is mostly
+    /// useful after connecting with [`Gopher::opportunistic_tls`], where
+    /// the scheme alone doesn't tell you whether the handshake actually
+    /// succeeded.
+    pub fn is_tls(&self) -> bool {
+        matches!(self, Self::Tls(_))
+    }
+
+    /// Fetch a resource given a path, decode it as UTF-8 (lossily), and
+    /// strip anything unsafe to print to a terminal: ASCII control bytes
+    /// below `0x20` (except `\t`, `\n`, `\r`) and ANSI CSI escape
+    /// sequences (`\x1b[...]`). Intended purely as a safe-to-display
+    /// convenience for text and menu content; use the raw [`fetch`] for
+    /// binary data.
+    ///
+    /// [`fetch`]: GopherConnection::fetch
+    pub fn fetch_text(&mut self, path: &str) -> Result<String, io::Error> {
+        let data = self.fetch(path)?;
+        let text = String::from_utf8_lossy(&data);
+        Ok(sanitize(&text))
+    }
+
+    /// Fetch a resource given a path and parse it as a Gopher menu.
+    ///
+    /// # Example
+    /// ```
+    /// use gophers::Gopher;
+    /// let gopher = Gopher::new("gophers://bitreich.org").unwrap();
+    /// let mut stream = gopher.connect().unwrap();
+    /// let menu = stream.fetch_menu("/").unwrap();
+    /// ```
+    pub fn fetch_menu(&mut self, path: &str) -> Result<Vec<MenuItem>, io::Error> {
+        let data = self.fetch(path)?;
+        Ok(parse_menu(&data))
+    }
+
+    /// Fetch a resource given a path, streaming the response in fixed-size
+    /// chunks directly into `dest` instead of buffering it all in memory.
+    /// Returns the total number of bytes written.
+    ///
+    /// `progress` is invoked after each chunk is written with the number
+    /// of bytes transferred so far; returning `false` aborts the download
+    /// early, leaving whatever was already written in `dest`.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use gophers::Gopher;
+    /// let gopher = Gopher::new("gophers://bitreich.org").unwrap();
+    /// let mut stream = gopher.connect().unwrap();
+    /// let mut out = vec![];
+    /// let total = stream
+    ///     .download("/memecache/index.meme", &mut out, |n| {
+    ///         println!("{n} bytes so far");
+    ///         true
+    ///     })
+    ///     .unwrap();
+    /// ```
+    pub fn download(
+        &mut self,
+        path: &str,
+        mut dest: impl Write,
+        mut progress: impl FnMut(usize) -> bool,
+    ) -> Result<usize, io::Error> {
+        let req = format!("{}\r\n", path);
+        self.write_all(req.as_bytes())?;
+
+        let mut buf = [0u8; 8192];
+        let mut total = 0;
+
+        loop {
+            let n = self.read(&mut buf)?;
+            if n == 0 {
+                break;
+            }
+
+            dest.write_all(&buf[..n])?;
+            total += n;
+
+            if !progress(total) {
+                break;
+            }
+        }
+
+        Ok(total)
+    }
+
+    /// Convenience wrapper around [`GopherConnection::download`] that
+    /// writes the response directly to a file at `dest_path` on disk.
+    pub fn download_to_file(
+        &mut self,
+        path: &str,
+        dest_path: impl AsRef<std::path::Path>,
+        progress: impl FnMut(usize) -> bool,
+    ) -> Result<usize, io::Error> {
+        let file = std::fs::File::create(dest_path)?;
+        self.download(path, file, progress)
+    }
+}
+
+/// Standard Gopher item type codes, as sent in the first byte of each
+/// menu line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ItemType {
+    /// '0' plain text file
+    Text,
+    /// '1' directory (submenu)
+    Directory,
+    /// '3' error
+    Error,
+    /// '7' search/index server
+    Search,
+    /// '9' binary file
+    Binary,
+    /// 'g' GIF image
+    Gif,
+    /// 'I' image
+    Image,
+    /// 'h' HTML document
+    Html,
+    /// 'i' informational text, not a selectable item
+    Info,
+    /// 'T' text-based telnet session
+    Telnet,
+    /// '+' redundant server / mirror
+    Mirror,
+    /// Any item type code not covered above
+    Unknown(u8),
+}
+
+impl From<u8> for ItemType {
+    fn from(code: u8) -> Self {
+        match code {
+            b'0' => Self::Text,
+            b'1' => Self::Directory,
+            b'3' => Self::Error,
+            b'7' => Self::Search,
+            b'9' => Self::Binary,
+            b'g' => Self::Gif,
+            b'I' => Self::Image,
+            b'h' => Self::Html,
+            b'i' => Self::Info,
+            b'T' => Self::Telnet,
+            b'+' => Self::Mirror,
+            other => Self::Unknown(other),
+        }
+    }
+}
+
+/// A single parsed entry of a Gopher menu.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MenuItem {
+    pub item_type: ItemType,
+    pub display: String,
+    pub selector: String,
+    pub host: String,
+    pub port: u16,
+}
+
+/// Parse a raw Gopher menu response into a list of [`MenuItem`]s.
+///
+/// The response is split into lines on CRLF (bare LF is accepted too), the
+/// first byte of each line is taken as
+/// the item type code, and the remainder is split on TABs into
+/// display/selector/host/port. Parsing stops at a lone `.` terminator
+/// line, and malformed lines are skipped rather than aborting the whole
+/// menu.
+pub fn parse_menu(data: &[u8]) -> Vec<MenuItem> {
+    let mut items = vec![];
+
+    for line in data.split(|&b| b == b'\n') {
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+
+        if line == b"." {
+            break;
+        }
+        if line.is_empty() {
+            continue;
+        }
+
+        let item_type = ItemType::from(line[0]);
+        let mut fields = line[1..].split(|&b| b == b'\t');
+
+        let (Some(display), Some(selector), Some(host), Some(port)) =
+            (fields.next(), fields.next(), fields.next(), fields.next())
+        else {
+            continue;
+        };
+
+        let Ok(port) = String::from_utf8_lossy(port).trim().parse::<u16>() else {
+            continue;
+        };
+
+        items.push(MenuItem {
+            item_type,
+            display: String::from_utf8_lossy(display).into_owned(),
+            selector: String::from_utf8_lossy(selector).into_owned(),
+            host: String::from_utf8_lossy(host).into_owned(),
+            port,
+        });
+    }
+
+    items
+}
+
+/// Strip ASCII control bytes below `0x20` (except `\t`, `\n`, `\r`) and
+/// ANSI CSI escape sequences (`\x1b[...]`) from `text`, so it is safe to
+/// print directly to a terminal.
+fn sanitize(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c == '\x1b' && chars.peek() == Some(&'[') {
+            chars.next(); // consume '['
+                          // CSI sequences end at the first byte in 0x40..=0x7e.
+            for c in chars.by_ref() {
+                if ('\x40'..='\x7e').contains(&c) {
+                    break;
+                }
+            }
+            continue;
+        }
+
+        if (c as u32) < 0x20 && !matches!(c, '\t' | '\n' | '\r') {
+            continue;
+        }
+
+        out.push(c);
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_menu_skips_malformed_lines_and_stops_at_terminator() {
+        let data = b"1Directory\t/dir\thost.example\t70\r\nmalformed line\r\n0Text\t/text\thost.example\t70\r\n.\r\n0after terminator\t/nope\thost.example\t70\r\n";
+        let items = parse_menu(data);
+
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].item_type, ItemType::Directory);
+        assert_eq!(items[0].selector, "/dir");
+        assert_eq!(items[1].item_type, ItemType::Text);
+        assert_eq!(items[1].port, 70);
+    }
+
+    #[test]
+    fn parse_menu_accepts_lf_only_lines() {
+        let data = b"0Text\t/a\thost.example\t70\n.\n";
+        let items = parse_menu(data);
+
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].host, "host.example");
+    }
+
+    #[test]
+    fn sanitize_keeps_tab_newline_and_cr_but_drops_other_control_bytes() {
+        assert_eq!(sanitize("a\x07b\tc\nd\re"), "ab\tc\nd\re");
+    }
+
+    #[test]
+    fn sanitize_strips_ansi_csi_escape_sequences() {
+        assert_eq!(sanitize("\x1b[31mred\x1b[0m text"), "red text");
+    }
+
+    #[test]
+    fn bare_host_strips_ipv6_brackets_only() {
+        assert_eq!(bare_host("[2001:db8::1]"), "2001:db8::1");
+        assert_eq!(bare_host("bitreich.org"), "bitreich.org");
+        assert_eq!(bare_host("127.0.0.1"), "127.0.0.1");
+    }
+
+    #[test]
+    fn socks5_address_bytes_encodes_ipv4_with_raw_address_type() {
+        let bytes = socks5_address_bytes("127.0.0.1").unwrap();
+        assert_eq!(bytes, vec![0x01, 127, 0, 0, 1]);
+    }
+
+    #[test]
+    fn socks5_address_bytes_encodes_bracketed_ipv6_with_raw_address_type() {
+        let bytes = socks5_address_bytes("[::1]").unwrap();
+        assert_eq!(
+            bytes,
+            vec![0x04, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1]
+        );
+    }
+
+    #[test]
+    fn socks5_address_bytes_encodes_domain_names_for_the_proxy_to_resolve() {
+        let bytes = socks5_address_bytes("bitreichxgczkzqp4ol.onion").unwrap();
+        assert_eq!(bytes[0], 0x03);
+        assert_eq!(bytes[1] as usize, "bitreichxgczkzqp4ol.onion".len());
+        assert_eq!(&bytes[2..], b"bitreichxgczkzqp4ol.onion");
+    }
 }